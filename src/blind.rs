@@ -34,13 +34,33 @@
 //! assert!(ok.is_ok());
 //! ```
 
-pub use crate::common::sign_hashed as sign;
-pub use crate::common::verify_hashed as verify;
+use crate::{BlindedDigest, Signature, Unblinder};
 use fdh::Digest;
 use num_bigint::BigUint;
 use rand::Rng;
 use rsa::internals;
-use rsa::PublicKey;
+use rsa::{PublicKey, PublicKeyParts};
+
+/// Sign a blinded digest, producing a blind signature.
+///
+/// The returned signature is blind: it signs `blinded_digest`, not the original digest, and
+/// must be passed through [`unblind`] before it will verify against the original digest.
+pub fn sign<R: Rng>(
+    rng: &mut R,
+    priv_key: &rsa::RsaPrivateKey,
+    blinded_digest: &BlindedDigest,
+) -> Result<Signature, crate::Error> {
+    crate::common::sign_hashed(rng, priv_key, blinded_digest.as_ref())
+}
+
+/// Verify a signature against a digest.
+pub fn verify<P: PublicKey>(
+    pub_key: &P,
+    digest: &[u8],
+    sig: &Signature,
+) -> Result<(), crate::Error> {
+    crate::common::verify_hashed(pub_key, digest, sig)
+}
 
 /// Hash the message as a Full Domain Hash
 pub fn hash_message<H: Digest + Clone, P: PublicKey>(
@@ -54,19 +74,89 @@ where
     Ok(result)
 }
 
+/// Number of bytes in the random prefix used by [`hash_message_randomized`].
+const RANDOMIZER_LEN: usize = 32;
+
+/// Hash the message as a Full Domain Hash, with a fresh random prefix folded in first.
+///
+/// This mirrors the message randomization RFC 9474 adds on top of plain blind RSA: prepending a
+/// fresh random value before hashing improves the security margin and unlinkability of a message
+/// that gets blind-signed more than once. Returns the digest together with the randomizer, which
+/// the caller must hold onto and pass to [`hash_message_randomized_verify`] to re-derive the same
+/// digest later.
+pub fn hash_message_randomized<H: Digest + Clone, P: PublicKey, R: Rng>(
+    rng: &mut R,
+    signer_public_key: &P,
+    message: &[u8],
+) -> Result<(Vec<u8>, [u8; RANDOMIZER_LEN]), crate::Error>
+where
+    H::OutputSize: Clone,
+{
+    let mut randomizer = [0u8; RANDOMIZER_LEN];
+    rng.fill_bytes(&mut randomizer);
+    let digest = hash_randomized_message::<H, P>(signer_public_key, message, &randomizer)?;
+    Ok((digest, randomizer))
+}
+
+/// Re-derive the digest produced by [`hash_message_randomized`], given the `randomizer` it
+/// returned.
+pub fn hash_message_randomized_verify<H: Digest + Clone, P: PublicKey>(
+    signer_public_key: &P,
+    message: &[u8],
+    randomizer: &[u8; RANDOMIZER_LEN],
+) -> Result<Vec<u8>, crate::Error>
+where
+    H::OutputSize: Clone,
+{
+    hash_randomized_message::<H, P>(signer_public_key, message, randomizer)
+}
+
+fn hash_randomized_message<H: Digest + Clone, P: PublicKey>(
+    signer_public_key: &P,
+    message: &[u8],
+    randomizer: &[u8; RANDOMIZER_LEN],
+) -> Result<Vec<u8>, crate::Error>
+where
+    H::OutputSize: Clone,
+{
+    let mut prefixed = Vec::with_capacity(RANDOMIZER_LEN + message.len());
+    prefixed.extend_from_slice(randomizer);
+    prefixed.extend_from_slice(message);
+    hash_message::<H, P>(signer_public_key, &prefixed)
+}
+
 /// Blind the given digest, returning the blinded digest and the unblinding factor.
-pub fn blind<R: Rng, P: PublicKey>(rng: &mut R, pub_key: P, digest: &[u8]) -> (Vec<u8>, Vec<u8>) {
+///
+/// The blinded digest is left-zero-padded to `pub_key`'s modulus size, the same canonical,
+/// fixed-width encoding [`sign`] and [`unblind`] use, so it is safe to put on the wire as-is.
+pub fn blind<R: Rng, P: PublicKey>(
+    rng: &mut R,
+    pub_key: P,
+    digest: &[u8],
+) -> (BlindedDigest, Unblinder) {
+    let modulus_len = pub_key.size();
     let c = BigUint::from_bytes_be(digest);
     let (c, unblinder) = internals::blind::<R, P>(rng, &pub_key, &c);
-    (c.to_bytes_be(), unblinder.to_bytes_be())
+    (
+        crate::common::pad_to_modulus(c.to_bytes_be(), modulus_len).into(),
+        unblinder.to_bytes_be().into(),
+    )
 }
 
 /// Unblind the given signature, producing a signature that also signs the unblided digest.
-pub fn unblind(pub_key: impl PublicKey, blinded_sig: &[u8], unblinder: &[u8]) -> Vec<u8> {
-    let blinded_sig = BigUint::from_bytes_be(blinded_sig);
-    let unblinder = BigUint::from_bytes_be(unblinder);
+///
+/// The result is left-zero-padded to `pub_key`'s modulus size, the same canonical, fixed-width
+/// encoding [`sign`] and [`blind`] use.
+pub fn unblind(
+    pub_key: impl PublicKey,
+    blinded_sig: &Signature,
+    unblinder: &Unblinder,
+) -> Signature {
+    let modulus_len = pub_key.size();
+    let blinded_sig = BigUint::from_bytes_be(blinded_sig.as_ref());
+    let unblinder = BigUint::from_bytes_be(unblinder.as_ref());
     let unblinded = internals::unblind(pub_key, &blinded_sig, &unblinder);
-    unblinded.to_bytes_be()
+    crate::common::pad_to_modulus(unblinded.to_bytes_be(), modulus_len).into()
 }
 
 #[cfg(test)]
@@ -77,6 +167,34 @@ mod tests {
     use rsa::{RsaPrivateKey, RsaPublicKey};
     use sha2::Sha256;
 
+    #[test]
+    fn hash_message_randomized_test() -> Result<(), Error> {
+        let mut rng = rand::thread_rng();
+        let message = b"NEVER GOING TO GIVE YOU UP";
+
+        let signer_priv_key = RsaPrivateKey::new(&mut rng, 256).unwrap();
+        let signer_pub_key = signer_priv_key.to_public_key();
+
+        let (digest_1, randomizer_1) =
+            blind::hash_message_randomized::<Sha256, _, _>(&mut rng, &signer_pub_key, message)?;
+        let (digest_2, randomizer_2) =
+            blind::hash_message_randomized::<Sha256, _, _>(&mut rng, &signer_pub_key, message)?;
+
+        // Different randomizers should (almost certainly) produce different digests.
+        assert!(randomizer_1 != randomizer_2);
+        assert!(digest_1 != digest_2);
+
+        // The digest is reproducible given the same randomizer.
+        let rederived = blind::hash_message_randomized_verify::<Sha256, _>(
+            &signer_pub_key,
+            message,
+            &randomizer_1,
+        )?;
+        assert_eq!(digest_1, rederived);
+
+        Ok(())
+    }
+
     #[test]
     fn blind_test() -> Result<(), Error> {
         // Stage 1: Setup
@@ -123,6 +241,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn fixed_width_encoding_test() -> Result<(), Error> {
+        let mut rng = rand::thread_rng();
+        let message = b"NEVER GOING TO GIVE YOU UP";
+
+        let signer_priv_key = RsaPrivateKey::new(&mut rng, 256).unwrap();
+        let signer_pub_key = signer_priv_key.to_public_key();
+        let modulus_len = signer_pub_key.size();
+
+        // Do this a bunch since only some digests happen to need padding.
+        for _ in 0..500 {
+            let digest = blind::hash_message::<Sha256, _>(&signer_pub_key, message)?;
+            let (blinded_digest, unblinder) = blind::blind(&mut rng, &signer_pub_key, &digest);
+            let blind_signature = blind::sign(&mut rng, &signer_priv_key, &blinded_digest)?;
+            let signature = blind::unblind(&signer_pub_key, &blind_signature, &unblinder);
+
+            assert_eq!(blinded_digest.as_bytes().len(), modulus_len);
+            assert_eq!(blind_signature.as_bytes().len(), modulus_len);
+            assert_eq!(signature.as_bytes().len(), modulus_len);
+
+            // Canonically-encoded values round-trip through the strict decoders.
+            let decoded = crate::BlindedDigest::from_modulus_bytes(
+                &signer_pub_key,
+                blinded_digest.as_bytes(),
+            )?;
+            assert!(decoded == blinded_digest);
+
+            let decoded =
+                crate::Signature::from_modulus_bytes(&signer_pub_key, signature.as_bytes())?;
+            assert!(decoded == signature);
+        }
+
+        // Anything shorter or longer than the modulus size is rejected outright.
+        assert!(
+            crate::Signature::from_modulus_bytes(&signer_pub_key, &vec![0u8; modulus_len - 1])
+                .is_err()
+        );
+        assert!(crate::Signature::from_modulus_bytes(
+            &signer_pub_key,
+            &vec![0xffu8; modulus_len + 1]
+        )
+        .is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn error_test() -> Result<(), Error> {
         // Stage 1: Setup
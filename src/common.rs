@@ -0,0 +1,178 @@
+//! Low-level signing and verification helpers shared by the regular and blind signature schemes.
+//!
+//! Both [`crate::sign`]/[`crate::verify`] and the hashing, signing and verifying functions in
+//! [`crate::blind`] are thin wrappers around the functions in this module (`blind::blind` and
+//! `blind::unblind` call `rsa::internals` directly): they differ only in *what* gets hashed and
+//! *when* blinding is applied, not in how the raw RSA operation itself is performed.
+
+use crate::types::Signature;
+use num_bigint::BigUint;
+use rand::Rng;
+use rsa::{internals, PublicKey, PublicKeyParts, RsaPrivateKey};
+use std::fmt;
+
+/// Errors that can occur while signing or verifying with RSA-FDH.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying `rsa` crate returned an error.
+    Rsa(rsa::errors::Error),
+    /// No full domain hash smaller than the modulus could be found, or a digest handed to the
+    /// signer was not smaller than the modulus.
+    ///
+    /// The first case should essentially never happen for a real-world modulus size, since it
+    /// requires exhausting all `u16` hash expansion attempts. The second happens whenever a
+    /// digest derived for a different (larger) key is signed.
+    DigestTooLarge,
+    /// The signature did not verify against the provided digest.
+    Verification,
+    /// A fixed-width encoded value was not exactly the modulus size, or did not decode to a
+    /// number smaller than the modulus.
+    InvalidEncoding,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Rsa(e) => write!(f, "rsa error: {}", e),
+            Error::DigestTooLarge => {
+                write!(f, "could not fit the full domain hash under the modulus")
+            }
+            Error::Verification => write!(f, "signature verification failed"),
+            Error::InvalidEncoding => write!(
+                f,
+                "value was not a canonical, modulus-sized big-endian encoding"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rsa::errors::Error> for Error {
+    fn from(e: rsa::errors::Error) -> Self {
+        Error::Rsa(e)
+    }
+}
+
+/// Hash `message` so that it can be used directly as the base of an RSA signature under
+/// `pub_key`: the digest is expanded with `H` until it is both `pub_key`'s modulus size in
+/// bytes and numerically smaller than the modulus.
+///
+/// Returns the digest along with the `iv`: the number of expansion rounds it took to land under
+/// the modulus. Verifiers never need to be told the `iv`, since re-hashing the same message
+/// against the same key deterministically finds the same one.
+pub fn hash_message<H, P>(pub_key: &P, message: &[u8]) -> Result<(Vec<u8>, u16), Error>
+where
+    H: digest::Digest + Clone,
+    P: PublicKey,
+    H::OutputSize: Clone,
+{
+    let modulus_len = pub_key.size();
+
+    for iv in 0..=u16::MAX {
+        let mut digest = Vec::with_capacity(modulus_len);
+        let mut block: u16 = 0;
+        while digest.len() < modulus_len {
+            let mut hasher = H::new();
+            hasher.update(iv.to_be_bytes());
+            hasher.update(block.to_be_bytes());
+            hasher.update(message);
+            digest.extend_from_slice(hasher.finalize().as_slice());
+            block = block.wrapping_add(1);
+        }
+        digest.truncate(modulus_len);
+
+        if &BigUint::from_bytes_be(&digest) < pub_key.n() {
+            return Ok((digest, iv));
+        }
+    }
+
+    Err(Error::DigestTooLarge)
+}
+
+/// Left-zero-pad `bytes` to `len` bytes.
+///
+/// `BigUint::to_bytes_be` drops leading zero bytes, so its output's length varies with the
+/// numeric value. Re-padding to the modulus size gives a canonical, fixed-width encoding that
+/// interops with wire formats used by other RSA blind-signature implementations.
+pub(crate) fn pad_to_modulus(mut bytes: Vec<u8>, len: usize) -> Vec<u8> {
+    if bytes.len() < len {
+        let mut padded = vec![0u8; len - bytes.len()];
+        padded.append(&mut bytes);
+        padded
+    } else {
+        bytes
+    }
+}
+
+/// Sign an already-hashed digest, producing a raw RSA signature (no signature padding scheme),
+/// left-zero-padded to the modulus size.
+///
+/// `hashed` is expected to be the output of [`hash_message`] (or an equivalently-derived full
+/// domain hash), and therefore already smaller than `priv_key`'s modulus.
+///
+/// The private-key operation is base-blinded: before `hashed` is exponentiated, a random `r` is
+/// folded into it (`m' = m * r^e mod n`) and divided back out of the result
+/// (`s = s' * r^-1 mod n`), using the same blind/unblind primitives [`crate::blind`] uses to hide
+/// the message itself. This means the exponentiation the signer performs never touches
+/// attacker-influenced input directly, which closes the timing/cache side channel that the
+/// `blinded` option on RSA-PSS signing keys exists to mitigate, while producing an identical
+/// signature. Use [`sign_hashed_unblinded`] (reached from the crate root through
+/// [`crate::sign_unblinded`], which hashes the message first) to opt out.
+pub fn sign_hashed<R: Rng>(
+    rng: &mut R,
+    priv_key: &RsaPrivateKey,
+    hashed: &[u8],
+) -> Result<Signature, Error> {
+    let c = BigUint::from_bytes_be(hashed);
+
+    if &c >= priv_key.n() {
+        return Err(Error::DigestTooLarge);
+    }
+
+    let pub_key = priv_key.to_public_key();
+    let (blinded, unblinder) = internals::blind(rng, &pub_key, &c);
+    let blinded_sig = internals::decrypt::<R>(None, priv_key, &blinded)?;
+    let s = internals::unblind(&pub_key, &blinded_sig, &unblinder);
+
+    Ok(pad_to_modulus(s.to_bytes_be(), priv_key.size()).into())
+}
+
+/// Sign an already-hashed digest like [`sign_hashed`], but without base-blinding the
+/// private-key operation.
+///
+/// Only use this if the input is already blinded by other means (as the blind-signature flow in
+/// [`crate::blind`] does for the message itself) and the cost of the extra blind/unblind pair in
+/// [`sign_hashed`] is unwelcome; otherwise prefer [`sign_hashed`].
+pub fn sign_hashed_unblinded<R: Rng>(
+    _rng: &mut R,
+    priv_key: &RsaPrivateKey,
+    hashed: &[u8],
+) -> Result<Signature, Error> {
+    let c = BigUint::from_bytes_be(hashed);
+
+    if &c >= priv_key.n() {
+        return Err(Error::DigestTooLarge);
+    }
+
+    let s = internals::decrypt::<R>(None, priv_key, &c)?;
+    Ok(pad_to_modulus(s.to_bytes_be(), priv_key.size()).into())
+}
+
+/// Verify a raw (un-padded) RSA signature against an already-hashed digest.
+pub fn verify_hashed<P: PublicKey>(
+    pub_key: &P,
+    hashed: &[u8],
+    sig: &Signature,
+) -> Result<(), Error> {
+    let c = BigUint::from_bytes_be(hashed);
+    let s = BigUint::from_bytes_be(sig.as_ref());
+
+    let m = s.modpow(pub_key.e(), pub_key.n());
+
+    if m == c {
+        Ok(())
+    } else {
+        Err(Error::Verification)
+    }
+}
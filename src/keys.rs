@@ -0,0 +1,178 @@
+//! [`SigningKey`] and [`VerifyingKey`] wrapper types implementing the [`signature`] crate's
+//! traits, the same way `rsa::pss` and `rsa::pkcs1v15` do. This lets RSA-FDH be used anywhere
+//! code is generic over `signature::Signer`/`Verifier` instead of forcing callers onto the
+//! bespoke [`crate::sign`]/[`crate::verify`] functions.
+
+use crate::common;
+use crate::types::Signature;
+use core::marker::PhantomData;
+use digest::Digest;
+use rand::{CryptoRng, RngCore};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use signature::{
+    DigestSigner, DigestVerifier, Keypair, RandomizedDigestSigner, RandomizedSigner, Signer,
+    Verifier,
+};
+
+/// An RSA-FDH signing key, generic over the digest `H` used for the Full Domain Hash.
+pub struct SigningKey<H: Digest + Clone> {
+    inner: RsaPrivateKey,
+    _digest: PhantomData<H>,
+}
+
+impl<H: Digest + Clone> SigningKey<H> {
+    /// Wrap an `RsaPrivateKey` as an RSA-FDH signing key.
+    pub fn new(key: RsaPrivateKey) -> Self {
+        SigningKey {
+            inner: key,
+            _digest: PhantomData,
+        }
+    }
+
+    /// Return the wrapped `RsaPrivateKey`.
+    pub fn into_inner(self) -> RsaPrivateKey {
+        self.inner
+    }
+}
+
+impl<H: Digest + Clone> Clone for SigningKey<H> {
+    fn clone(&self) -> Self {
+        SigningKey {
+            inner: self.inner.clone(),
+            _digest: PhantomData,
+        }
+    }
+}
+
+impl<H: Digest + Clone> Keypair for SigningKey<H>
+where
+    H::OutputSize: Clone,
+{
+    type VerifyingKey = VerifyingKey<H>;
+
+    fn verifying_key(&self) -> VerifyingKey<H> {
+        VerifyingKey::new(self.inner.to_public_key())
+    }
+}
+
+impl<H: Digest + Clone> RandomizedSigner<Signature> for SigningKey<H>
+where
+    H::OutputSize: Clone,
+{
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        msg: &[u8],
+    ) -> Result<Signature, signature::Error> {
+        let public_key = self.inner.to_public_key();
+        let (hashed, _iv) = common::hash_message::<H, RsaPublicKey>(&public_key, msg)
+            .map_err(signature::Error::from_source)?;
+        common::sign_hashed(rng, &self.inner, &hashed).map_err(signature::Error::from_source)
+    }
+}
+
+impl<H: Digest + Clone> Signer<Signature> for SigningKey<H>
+where
+    H::OutputSize: Clone,
+{
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.try_sign_with_rng(&mut rand::rngs::OsRng, msg)
+    }
+}
+
+impl<H: Digest + Clone> RandomizedDigestSigner<H, Signature> for SigningKey<H>
+where
+    H::OutputSize: Clone,
+{
+    fn try_sign_digest_with_rng(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        digest: H,
+    ) -> Result<Signature, signature::Error> {
+        self.try_sign_with_rng(rng, &digest.finalize())
+    }
+}
+
+/// An RSA-FDH verifying key, generic over the digest `H` used for the Full Domain Hash.
+pub struct VerifyingKey<H: Digest + Clone> {
+    inner: RsaPublicKey,
+    _digest: PhantomData<H>,
+}
+
+impl<H: Digest + Clone> VerifyingKey<H> {
+    /// Wrap an `RsaPublicKey` as an RSA-FDH verifying key.
+    pub fn new(key: RsaPublicKey) -> Self {
+        VerifyingKey {
+            inner: key,
+            _digest: PhantomData,
+        }
+    }
+
+    /// Return the wrapped `RsaPublicKey`.
+    pub fn into_inner(self) -> RsaPublicKey {
+        self.inner
+    }
+}
+
+impl<H: Digest + Clone> Clone for VerifyingKey<H> {
+    fn clone(&self) -> Self {
+        VerifyingKey {
+            inner: self.inner.clone(),
+            _digest: PhantomData,
+        }
+    }
+}
+
+impl<H: Digest + Clone> Verifier<Signature> for VerifyingKey<H>
+where
+    H::OutputSize: Clone,
+{
+    fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), signature::Error> {
+        let (hashed, _iv) = common::hash_message::<H, RsaPublicKey>(&self.inner, msg)
+            .map_err(signature::Error::from_source)?;
+        common::verify_hashed(&self.inner, &hashed, signature)
+            .map_err(signature::Error::from_source)
+    }
+}
+
+impl<H: Digest + Clone> DigestVerifier<H, Signature> for VerifyingKey<H>
+where
+    H::OutputSize: Clone,
+{
+    fn verify_digest(&self, digest: H, signature: &Signature) -> Result<(), signature::Error> {
+        self.verify(&digest.finalize(), signature)
+    }
+}
+
+impl<H: Digest + Clone> DigestSigner<H, Signature> for SigningKey<H>
+where
+    H::OutputSize: Clone,
+{
+    fn try_sign_digest(&self, digest: H) -> Result<Signature, signature::Error> {
+        self.try_sign(&digest.finalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Signature, SigningKey, VerifyingKey};
+    use rsa::RsaPrivateKey;
+    use sha2::Sha256;
+    use signature::{Keypair, RandomizedSigner, Signer, Verifier};
+
+    #[test]
+    fn signer_verifier_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let message = b"NEVER GOING TO GIVE YOU UP";
+
+        let signing_key: SigningKey<Sha256> =
+            SigningKey::new(RsaPrivateKey::new(&mut rng, 256).unwrap());
+        let verifying_key: VerifyingKey<Sha256> = signing_key.verifying_key();
+
+        let signature: Signature = signing_key.sign_with_rng(&mut rng, message);
+        assert!(verifying_key.verify(message, &signature).is_ok());
+
+        let signature: Signature = signing_key.sign(message);
+        assert!(verifying_key.verify(message, &signature).is_ok());
+    }
+}
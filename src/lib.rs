@@ -38,8 +38,12 @@ use rsa::{PublicKey, RsaPrivateKey, RsaPublicKey};
 
 pub mod blind;
 mod common;
+mod keys;
+mod types;
 
 pub use common::Error;
+pub use keys::{SigningKey, VerifyingKey};
+pub use types::{BlindedDigest, Signature, Unblinder};
 
 /// Sign a message.
 ///
@@ -50,7 +54,7 @@ pub fn sign<H: digest::Digest + Clone, R: Rng>(
     rng: &mut R,
     priv_key: &RsaPrivateKey,
     message: &[u8],
-) -> Result<Vec<u8>, Error>
+) -> Result<Signature, Error>
 where
     H::OutputSize: Clone,
 {
@@ -60,13 +64,33 @@ where
     common::sign_hashed(rng, priv_key, &hashed)
 }
 
+/// Sign a message like [`sign`], but without base-blinding the private-key operation.
+///
+/// This skips the blind/unblind pair [`sign`] uses to hide the digest from the RSA
+/// exponentiation, so it is faster but reopens the timing/cache side-channel that base-blinding
+/// closes. Only use this if that side-channel is not a concern for your threat model (for
+/// example, the key never signs attacker-influenced messages); otherwise prefer [`sign`].
+pub fn sign_unblinded<H: digest::Digest + Clone, R: Rng>(
+    rng: &mut R,
+    priv_key: &RsaPrivateKey,
+    message: &[u8],
+) -> Result<Signature, Error>
+where
+    H::OutputSize: Clone,
+{
+    let public_key = priv_key.to_public_key();
+    let (hashed, _iv) = common::hash_message::<H, RsaPublicKey>(&public_key, message)?;
+
+    common::sign_hashed_unblinded(rng, priv_key, &hashed)
+}
+
 /// Verify a signature.
 ///
 /// Generally the message should be hashed before verifying the digest against the provided signature.
 pub fn verify<H: digest::Digest + Clone, K: PublicKey>(
     pub_key: &K,
     message: &[u8],
-    sig: &[u8],
+    sig: &Signature,
 ) -> Result<(), Error>
 where
     H::OutputSize: Clone,
@@ -124,15 +148,15 @@ mod tests {
         let signature_1 = rsa_fdh::sign::<Sha256, _>(&mut rng, &key_1, &digest)?;
 
         let key_2 = RsaPrivateKey::new(&mut rng, 512).unwrap();
-        let public_2 = key_1.to_public_key();
+        let public_2 = key_2.to_public_key();
         let signature_2 = rsa_fdh::sign::<Sha256, _>(&mut rng, &key_2, &digest)?;
 
         // Assert that signatures are different
         assert!(signature_1 != signature_2);
 
         // Assert that they don't cross validate
-        assert!(rsa_fdh::verify::<Sha256, _>(&public_1, &signature_2, &digest).is_err());
-        assert!(rsa_fdh::verify::<Sha256, _>(&public_2, &signature_1, &digest).is_err());
+        assert!(rsa_fdh::verify::<Sha256, _>(&public_1, &digest, &signature_2).is_err());
+        assert!(rsa_fdh::verify::<Sha256, _>(&public_2, &digest, &signature_1).is_err());
 
         Ok(())
     }
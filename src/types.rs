@@ -0,0 +1,235 @@
+//! Strongly-typed wrappers around the byte buffers that flow through the regular and blind
+//! signing APIs.
+//!
+//! Before these existed, [`crate::sign`], [`crate::verify`], and every function in
+//! [`crate::blind`] passed bare `Vec<u8>`/`&[u8]` around, which made it easy to hand a signature
+//! to a parameter that expected an unblinding factor (or vice versa) and left secret blinding
+//! factors lingering in memory after use. [`Signature`], [`BlindedDigest`], and [`Unblinder`]
+//! give each role its own type, and [`Unblinder`] zeroizes its bytes on drop since it is as
+//! sensitive as the blinding itself.
+
+use num_bigint::BigUint;
+use rsa::PublicKeyParts;
+use std::convert::Infallible;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Decode a canonical, fixed-width big-endian encoding, rejecting anything that is not exactly
+/// `pub_key`'s modulus size in bytes or that does not decode to a number smaller than the
+/// modulus.
+fn decode_modulus_sized<P: PublicKeyParts>(
+    pub_key: &P,
+    bytes: &[u8],
+) -> Result<Vec<u8>, crate::Error> {
+    if bytes.len() != pub_key.size() {
+        return Err(crate::Error::InvalidEncoding);
+    }
+
+    if &BigUint::from_bytes_be(bytes) >= pub_key.n() {
+        return Err(crate::Error::InvalidEncoding);
+    }
+
+    Ok(bytes.to_vec())
+}
+
+/// A raw RSA-FDH signature.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Signature(Vec<u8>);
+
+impl Signature {
+    /// Borrow the underlying bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Decode a canonical, fixed-width big-endian signature for `pub_key`.
+    ///
+    /// `bytes` must be exactly `pub_key`'s modulus size and decode to a number smaller than the
+    /// modulus, matching the encoding [`crate::sign`] and [`crate::blind::sign`] produce.
+    pub fn from_modulus_bytes<P: PublicKeyParts>(
+        pub_key: &P,
+        bytes: &[u8],
+    ) -> Result<Self, crate::Error> {
+        decode_modulus_sized(pub_key, bytes).map(Signature)
+    }
+}
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Signature {
+    fn from(bytes: Vec<u8>) -> Self {
+        Signature(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Signature(bytes.to_vec()))
+    }
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Signature").field(&self.0).finish()
+    }
+}
+
+impl fmt::LowerHex for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::UpperHex for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl signature::SignatureEncoding for Signature {
+    type Repr = Vec<u8>;
+}
+
+/// A blinded full-domain-hash digest, ready to be sent to the signer in the blind-signing flow.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BlindedDigest(Vec<u8>);
+
+impl BlindedDigest {
+    /// Borrow the underlying bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Decode a canonical, fixed-width big-endian blinded digest for `pub_key`.
+    ///
+    /// `bytes` must be exactly `pub_key`'s modulus size and decode to a number smaller than the
+    /// modulus, matching the encoding [`crate::blind::blind`] produces.
+    pub fn from_modulus_bytes<P: PublicKeyParts>(
+        pub_key: &P,
+        bytes: &[u8],
+    ) -> Result<Self, crate::Error> {
+        decode_modulus_sized(pub_key, bytes).map(BlindedDigest)
+    }
+}
+
+impl AsRef<[u8]> for BlindedDigest {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for BlindedDigest {
+    fn from(bytes: Vec<u8>) -> Self {
+        BlindedDigest(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for BlindedDigest {
+    type Error = Infallible;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(BlindedDigest(bytes.to_vec()))
+    }
+}
+
+impl fmt::Debug for BlindedDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BlindedDigest").field(&self.0).finish()
+    }
+}
+
+impl fmt::LowerHex for BlindedDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::UpperHex for BlindedDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// The secret blinding factor produced by [`crate::blind::blind`] and consumed by
+/// [`crate::blind::unblind`].
+///
+/// Anyone who recovers this value can link a blind signature back to the blinded digest it came
+/// from, so its bytes are zeroized as soon as it is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Unblinder(Vec<u8>);
+
+impl Unblinder {
+    /// Borrow the underlying bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Unblinder {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Unblinder {
+    fn from(bytes: Vec<u8>) -> Self {
+        Unblinder(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Unblinder {
+    type Error = Infallible;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Unblinder(bytes.to_vec()))
+    }
+}
+
+impl fmt::Debug for Unblinder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Deliberately do not print the bytes: this is the one type here that's actually secret.
+        f.debug_tuple("Unblinder").field(&"<redacted>").finish()
+    }
+}
+
+impl fmt::LowerHex for Unblinder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::UpperHex for Unblinder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Unblinder {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}